@@ -4,7 +4,83 @@ pub use gfx_glyph::{FontId, HorizontalAlign, Scale, VerticalAlign};
 use gfx_glyph::{self, GlyphPositioner, SectionText, VariedSection};
 use rusttype::{point, PositionedGlyph};
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::f32;
+use unicode_bidi::BidiInfo;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Base paragraph direction used to seed the Unicode Bidirectional Algorithm.
+/// `Auto` lets the algorithm pick a direction from the first strongly-directional
+/// character, which is the right choice unless the surrounding UI dictates one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+    Auto,
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Direction::Auto
+    }
+}
+
+/// Vertical font metrics for a given font at a given `Scale`, pulled straight from the
+/// underlying `rusttype` font. Useful for laying out or vertically centering text without
+/// having to measure glyphs first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontMetrics {
+    /// Distance from the baseline to the top of the tallest glyph, in pixels.
+    pub ascent: f32,
+    /// Distance from the baseline to the bottom of the lowest-hanging glyph, in pixels.
+    /// This is negative, following `rusttype`'s convention.
+    pub descent: f32,
+    /// Additional spacing recommended between lines, on top of `ascent - descent`.
+    pub line_gap: f32,
+    /// `ascent - descent + line_gap`; the distance from one line's baseline to the next.
+    pub line_height: f32,
+}
+
+/// A fragment resolved against `TextCached`'s defaults, with its byte range recorded
+/// against the concatenated, logical-order `contents` string.
+struct ResolvedFragment {
+    start: usize,
+    end: usize,
+    color: Color,
+    font_id: FontId,
+    scale: Scale,
+    embolden: Option<f32>,
+}
+
+/// A run of text in final, visual display order, still carrying its resolved style and
+/// its byte range back in the logical-order `contents` string (`source_start`/`source_end`),
+/// so glyph-level hit testing can map a displayed glyph back to an insertion index.
+struct TextPiece {
+    text: String,
+    color: Color,
+    font_id: FontId,
+    scale: Scale,
+    embolden: Option<f32>,
+    source_start: usize,
+    source_end: usize,
+    is_rtl: bool,
+}
+
+/// The synthetic style requested for one `SectionText`, mirroring its index in the
+/// `VariedSection` built by `generate_varied_section`.
+#[derive(Clone, Copy, Debug, Default)]
+struct SyntheticStyle {
+    embolden: Option<f32>,
+}
+
+/// Per-piece bookkeeping needed to map a positioned glyph back to a byte index in the
+/// logical-order `contents` string, mirroring the `SectionText` it was built from.
+#[derive(Clone, Copy, Debug)]
+struct PieceMeta {
+    source_start: usize,
+    source_end: usize,
+    is_rtl: bool,
+}
 
 /// Aliased type from `gfx_glyph`.
 pub type Layout = gfx_glyph::Layout<gfx_glyph::BuiltInLineBreaker>;
@@ -25,6 +101,15 @@ pub struct TextFragment {
     pub font_id: Option<FontId>,
     /// Fragment's scale, defaults to text's scale.
     pub scale: Option<Scale>,
+    /// Synthetic ("faux") bold weight, in pixels of outward dilation. `None` renders the
+    /// glyphs as shipped by the font.
+    ///
+    /// Synthetic oblique/italic (shearing a font's upright glyphs) was requested alongside
+    /// this and is intentionally not implemented: a `GlyphPositioner`, which is where
+    /// `embolden` hooks in, can only reposition already-shaped `PositionedGlyph`s, not shear
+    /// their outlines - doing that needs a rasterization-time outline transform that this
+    /// module doesn't have a hook for. Revisit if/when the glyph pipeline exposes one.
+    pub embolden: Option<f32>,
 }
 
 impl Default for TextFragment {
@@ -34,10 +119,20 @@ impl Default for TextFragment {
             color: None,
             font_id: None,
             scale: None,
+            embolden: None,
         }
     }
 }
 
+impl TextFragment {
+    /// Builder-style setter for `embolden`, synthesizing a bold weight for fonts that
+    /// don't ship one.
+    pub fn embolden(mut self, amount: f32) -> Self {
+        self.embolden = Some(amount);
+        self
+    }
+}
+
 impl From<String> for TextFragment {
     fn from(text: String) -> TextFragment {
         TextFragment {
@@ -108,6 +203,43 @@ impl From<(Point2, f32)> for DrawParam {
     }
 }
 
+/// Resolves the named fonts and colors an inline markup string (`TextCached::from_markup`)
+/// can refer to, e.g. `[font=title]` or `[color=warn]`, in addition to literal `#rrggbb`
+/// colors, which markup can always use without registering anything here.
+#[derive(Clone, Debug, Default)]
+pub struct StyleTable {
+    fonts: HashMap<String, FontId>,
+    colors: HashMap<String, Color>,
+}
+
+impl StyleTable {
+    /// Creates an empty `StyleTable`; markup that refers to an unregistered name falls
+    /// back to being emitted as literal text, same as any other malformed tag.
+    pub fn new() -> StyleTable {
+        StyleTable::default()
+    }
+
+    /// Registers a named font for `[font=name]` tags to resolve to.
+    pub fn add_font(&mut self, name: &str, font_id: FontId) -> &mut StyleTable {
+        self.fonts.insert(name.to_string(), font_id);
+        self
+    }
+
+    /// Registers a named color for `[color=name]` tags to resolve to.
+    pub fn add_color(&mut self, name: &str, color: Color) -> &mut StyleTable {
+        self.colors.insert(name.to_string(), color);
+        self
+    }
+
+    fn font(&self, name: &str) -> Option<FontId> {
+        self.fonts.get(name).cloned()
+    }
+
+    fn color(&self, name: &str) -> Option<Color> {
+        self.colors.get(name).cloned()
+    }
+}
+
 /// Drawable text.
 /// Can be either monolithic, or consist of differently-formatted fragments.
 #[derive(Clone, Debug)]
@@ -122,6 +254,10 @@ pub struct TextCached {
     cached_string: Option<String>,
     cached_width: Option<u32>,
     cached_height: Option<u32>,
+    cached_line_count: Option<usize>,
+    cached_glyphs: Option<Vec<(usize, Rect)>>,
+    cached_carets: Option<Vec<(usize, Rect)>>,
+    base_direction: Direction,
 }
 
 impl Default for TextCached {
@@ -136,10 +272,119 @@ impl Default for TextCached {
             cached_string: None,
             cached_width: None,
             cached_height: None,
+            cached_line_count: None,
+            cached_glyphs: None,
+            cached_carets: None,
+            base_direction: Direction::default(),
         }
     }
 }
 
+/// The ring of unit offsets `apply_synthetics` stamps an emboldened glyph's copies at,
+/// scaled by the fragment's requested `embolden` amount. Axis-aligned offsets alone leave
+/// a visible hollow center once `amount` gets much past a pixel; the four diagonals fill
+/// that in without adding enough stamps to look like separate ghost glyphs.
+const EMBOLDEN_RING: [(f32, f32); 8] = [
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (0.0, 1.0),
+    (0.0, -1.0),
+    (0.707, 0.707),
+    (0.707, -0.707),
+    (-0.707, 0.707),
+    (-0.707, -0.707),
+];
+
+/// A `GlyphPositioner` that wraps `TextCached`'s own `Layout` and, after it has positioned
+/// glyphs normally, nudges the glyphs belonging to fragments that asked for synthetic bold
+/// styling. `styles[i]` corresponds to the `i`th `SectionText` passed in, the same order
+/// `generate_varied_section` built them in.
+///
+/// This is only ever constructed when at least one fragment requested `embolden`; plain
+/// text keeps using `self.layout` directly so the common case pays nothing extra.
+struct SyntheticGlyphPositioner<'s> {
+    inner: Layout,
+    styles: &'s [SyntheticStyle],
+    /// When `true`, the extra stamps `apply_synthetics` would normally add for `embolden`
+    /// are skipped, leaving only the glyphs `self.inner` positioned. Set for the
+    /// measurement-only callers (`calculate_dimensions`, `calculate_glyph_geometry`,
+    /// `calculate_caret_geometry`) so the
+    /// stamps - which exist purely to dilate the rendered coverage - don't inflate the
+    /// measured width/height or break the 1:1 glyph/grapheme mapping
+    /// `calculate_glyph_geometry` relies on.
+    measuring: bool,
+}
+
+impl<'s> std::hash::Hash for SyntheticGlyphPositioner<'s> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+        self.measuring.hash(state);
+        for style in self.styles {
+            style.embolden.map(f32::to_bits).hash(state);
+        }
+    }
+}
+
+impl<'s> SyntheticGlyphPositioner<'s> {
+    /// Nudges the positioned glyphs of each `GlyphedSectionText` according to the matching
+    /// `SyntheticStyle`. Embolden stamps each glyph a handful of extra times around a small
+    /// ring, which dilates the visible coverage the way a cheap faux-bold implementation
+    /// does when the outline itself isn't available to stroke directly.
+    fn apply_synthetics<'f>(&self, glyphed: &mut Vec<gfx_glyph::GlyphedSectionText<'f>>) {
+        if self.measuring {
+            return;
+        }
+        for (glyphed_text, style) in glyphed.iter_mut().zip(self.styles) {
+            let amount = match style.embolden {
+                Some(amount) => amount,
+                None => continue,
+            };
+            let gfx_glyph::GlyphedSectionText(positioned_glyphs, ..) = glyphed_text;
+            let stamped: Vec<PositionedGlyph> = positioned_glyphs
+                .iter()
+                .flat_map(|glyph| {
+                    let pos = glyph.position();
+                    EMBOLDEN_RING.iter().map(move |(dx, dy)| {
+                        glyph
+                            .clone()
+                            .into_unpositioned()
+                            .positioned(point(pos.x + dx * amount, pos.y + dy * amount))
+                    })
+                })
+                .collect();
+            positioned_glyphs.extend(stamped);
+        }
+    }
+}
+
+impl<'s> GlyphPositioner for SyntheticGlyphPositioner<'s> {
+    fn calculate_glyphs<'f>(
+        &self,
+        fonts: &'f [rusttype::Font],
+        section: &VariedSection<'f>,
+    ) -> Vec<gfx_glyph::GlyphedSectionText<'f>> {
+        let mut glyphed = self.inner.calculate_glyphs(fonts, section);
+        self.apply_synthetics(&mut glyphed);
+        glyphed
+    }
+
+    fn bounds_rect(&self, section: &VariedSection) -> rusttype::Rect<f32> {
+        self.inner.bounds_rect(section)
+    }
+
+    fn recalculate_glyphs<'f>(
+        &self,
+        previous: Vec<gfx_glyph::GlyphedSectionText<'f>>,
+        change: gfx_glyph::GlyphChange,
+        fonts: &'f [rusttype::Font],
+        section: &VariedSection<'f>,
+    ) -> Vec<gfx_glyph::GlyphedSectionText<'f>> {
+        let mut glyphed = self.inner.recalculate_glyphs(previous, change, fonts, section);
+        self.apply_synthetics(&mut glyphed);
+        glyphed
+    }
+}
+
 impl TextCached {
     // TODO: consider ditching context - it's here for consistency's sake, that's it.
     /// Creates a `TextCached` from a `TextFragment`.
@@ -157,6 +402,30 @@ impl TextCached {
         Ok(TextCached::default())
     }
 
+    /// Parses a small inline markup language into fragments and builds a `TextCached` from
+    /// them, so callers don't have to hand-assemble a `Vec<TextFragment>` for colored/sized
+    /// spans. Supported tags, each closed by its matching `[/tag]`:
+    ///
+    /// - `[color=#rrggbb]` or `[color=name]` (`name` resolved via `styles`)
+    /// - `[scale=N]`
+    /// - `[font=name]` (`name` resolved via `styles`)
+    ///
+    /// Unrecognized or malformed tags are emitted as literal text rather than erroring,
+    /// since a typo in markup shouldn't take down rendering; only unbalanced nesting (an
+    /// opening tag with no matching close) is reported as an error.
+    pub fn from_markup(
+        context: &mut Context,
+        markup: &str,
+        styles: &StyleTable,
+    ) -> GameResult<TextCached> {
+        let fragments = parse_markup(markup, styles)?;
+        let mut text = TextCached::new_empty(context)?;
+        for fragment in fragments {
+            text.add_fragment(fragment);
+        }
+        Ok(text)
+    }
+
     /// Appends a `TextFragment`.
     pub fn add_fragment<F>(&mut self, fragment: F) -> &mut TextCached
     where
@@ -202,14 +471,29 @@ impl TextCached {
         self
     }
 
-    fn generate_varied_section<'a>(
-        &'a self,
+    /// Sets the base paragraph direction used by the Unicode Bidirectional Algorithm
+    /// when laying out mixed-direction or right-to-left text. Defaults to `Direction::Auto`.
+    pub fn set_base_direction(&mut self, direction: Direction) -> &mut TextCached {
+        self.base_direction = direction;
+        self.invalidate_caches();
+        self
+    }
+
+    /// Resolves, for every fragment, the color/font/scale/synthetic-style that will
+    /// actually be used, alongside the fragment's byte range within the concatenated
+    /// `contents` string.
+    fn resolve_fragments(
+        &self,
         context: &Context,
-        relative_dest: Point2,
         color: Option<Color>,
-    ) -> VariedSection<'a> {
-        let mut sections = Vec::new();
+    ) -> (String, Vec<ResolvedFragment>) {
+        let mut contents = String::new();
+        let mut resolved = Vec::with_capacity(self.fragments.len());
         for fragment in &self.fragments {
+            let start = contents.len();
+            contents.push_str(&fragment.text);
+            let end = contents.len();
+
             let color = match fragment.color {
                 Some(c) => c,
                 None => match color {
@@ -225,13 +509,149 @@ impl TextCached {
                 Some(scale) => scale,
                 None => self.font_scale,
             };
-            sections.push(SectionText {
-                text: &fragment.text,
-                color: <[f32; 4]>::from(color),
+            resolved.push(ResolvedFragment {
+                start,
+                end,
+                color,
                 font_id,
                 scale,
+                embolden: fragment.embolden,
+            });
+        }
+        (contents, resolved)
+    }
+
+    /// Reorders resolved fragments into visual (left-to-right) order following the Unicode
+    /// Bidirectional Algorithm, splitting fragments at run boundaries so formatting is
+    /// preserved across the split. Characters within right-to-left runs are reversed by
+    /// grapheme cluster so combining marks and other clusters are never torn apart.
+    ///
+    /// Pieces are always returned as owned `String`s (rather than borrowing `contents`)
+    /// since right-to-left runs must be materialized in reversed order anyway; this keeps
+    /// the two cases uniform for the caller.
+    ///
+    /// Reordering happens per explicit (`\n`-separated) paragraph, which `BidiInfo` already
+    /// splits on - without wrapping that is exactly one display line per paragraph, so this
+    /// is a correct per-display-line reordering.
+    ///
+    /// With `set_bounds` wrapping active, a single paragraph can still span multiple
+    /// *visual* lines once `gfx_glyph`'s line breaker wraps it; the Bidirectional
+    /// Algorithm's line-reordering step (UAX #9) properly wants those wrapped-line
+    /// boundaries up front, which aren't known until `gfx_glyph` lays the (already
+    /// reordered) text out. So a wrapped multi-line RTL/mixed paragraph may wrap at a
+    /// different column than true per-visual-line bidi would choose. That's an accepted
+    /// approximation, not a reason to skip reordering altogether: leaving all wrapped text
+    /// in logical order would make every wrapped RTL paragraph render backwards, which is
+    /// strictly worse than an occasionally-off wrap point on a paragraph that needed more
+    /// than one visual line.
+    fn reorder_bidi(&self, contents: &str, resolved: &[ResolvedFragment]) -> Vec<TextPiece> {
+        if contents.is_empty() {
+            return Vec::new();
+        }
+
+        let base_level = match self.base_direction {
+            Direction::LeftToRight => Some(unicode_bidi::Level::ltr()),
+            Direction::RightToLeft => Some(unicode_bidi::Level::rtl()),
+            Direction::Auto => None,
+        };
+        let bidi_info = BidiInfo::new(contents, base_level);
+
+        let mut pieces = Vec::new();
+        for para in &bidi_info.paragraphs {
+            let (levels, runs) = bidi_info.visual_runs(para, para.range.clone());
+            for run in runs {
+                let is_rtl = levels[run.start].is_rtl();
+                // Fragments overlapping this run, in logical (reading) order.
+                let mut segments: Vec<(usize, usize, &str, &ResolvedFragment)> = resolved
+                    .iter()
+                    .filter_map(|fragment| {
+                        let seg_start = fragment.start.max(run.start);
+                        let seg_end = fragment.end.min(run.end);
+                        if seg_start < seg_end {
+                            Some((
+                                seg_start,
+                                seg_end,
+                                &contents[seg_start..seg_end],
+                                fragment,
+                            ))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                if is_rtl {
+                    // The whole run displays right-to-left: visit its fragments back to
+                    // front, and reverse each fragment's own characters too.
+                    segments.reverse();
+                }
+                for (seg_start, seg_end, text, fragment) in segments {
+                    let text = if is_rtl {
+                        text.graphemes(true).rev().collect()
+                    } else {
+                        text.to_string()
+                    };
+                    pieces.push(TextPiece {
+                        text,
+                        source_start: seg_start,
+                        source_end: seg_end,
+                        is_rtl,
+                        color: fragment.color,
+                        font_id: fragment.font_id,
+                        scale: fragment.scale,
+                        embolden: fragment.embolden,
+                    });
+                }
+            }
+        }
+        pieces
+    }
+
+    /// Builds the `VariedSection` used for both layout measurement and queuing, along with
+    /// the per-piece synthetic style (embolden) needed to pick a glyph positioner, and
+    /// the per-piece source metadata needed to map glyphs back to logical byte indices.
+    /// `text_arena` owns the (possibly BiDi-reordered) text backing each `SectionText`;
+    /// it must outlive the returned `VariedSection`.
+    ///
+    /// `apply_baseline_offset` should be `true` for the actual draw path (`queue`) and
+    /// `false` for measurement-only callers (`calculate_dimensions`, `calculate_glyph_geometry`,
+    /// `calculate_caret_geometry`):
+    /// see the comment below on `relative_dest.y` for why.
+    fn generate_varied_section<'a>(
+        &self,
+        context: &Context,
+        relative_dest: Point2,
+        color: Option<Color>,
+        text_arena: &'a mut Vec<String>,
+        apply_baseline_offset: bool,
+    ) -> (VariedSection<'a>, Vec<SyntheticStyle>, Vec<PieceMeta>) {
+        let (contents, resolved) = self.resolve_fragments(context, color);
+        let pieces = self.reorder_bidi(&contents, &resolved);
+        let first_piece_style = pieces.first().map(|piece| (piece.font_id, piece.scale));
+        let mut styles = Vec::with_capacity(pieces.len());
+        let mut synthetic = Vec::with_capacity(pieces.len());
+        let mut metas = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            metas.push(PieceMeta {
+                source_start: piece.source_start,
+                source_end: piece.source_end,
+                is_rtl: piece.is_rtl,
+            });
+            text_arena.push(piece.text);
+            styles.push((piece.color, piece.font_id, piece.scale));
+            synthetic.push(SyntheticStyle {
+                embolden: piece.embolden,
             });
         }
+        let sections: Vec<SectionText> = text_arena
+            .iter()
+            .zip(styles)
+            .map(|(text, (color, font_id, scale))| SectionText {
+                text,
+                color: <[f32; 4]>::from(color),
+                font_id,
+                scale,
+            })
+            .collect();
         let relative_dest = (
             {
                 // This positions text within bounds with relative_dest being to the left, always.
@@ -248,31 +668,66 @@ impl TextCached {
                 }
                 dest_x
             },
-            relative_dest.y,
+            // gfx_glyph anchors `screen_position` at the baseline of the first line, but
+            // `relative_dest` is documented (and expected, per `DrawParam::offset`) to mean
+            // the visual top-left of the text box, so queuing shifts down by the ascent of
+            // the first piece's own font/scale (not just this `TextCached`'s defaults, since
+            // a fragment can override both). Measurement callers pass
+            // `apply_baseline_offset: false`: they read back pixel bounding boxes from
+            // `calculate_glyphs`, which already accounts for gfx_glyph's own top-alignment,
+            // so adding the ascent again here would double it, inflating `height()` and
+            // `line_count()` by roughly one ascent and shifting every measured glyph rect
+            // down to match.
+            if apply_baseline_offset {
+                let (font_id, scale) = first_piece_style.unwrap_or((self.font_id, self.font_scale));
+                relative_dest.y + self.font_metrics_for(context, font_id, scale).ascent
+            } else {
+                relative_dest.y
+            },
         );
-        VariedSection {
+        let section = VariedSection {
             screen_position: relative_dest,
             bounds: (self.bounds.x, self.bounds.y),
             //z: f32,
             layout: self.layout,
             text: sections,
             ..Default::default()
-        }
+        };
+        (section, synthetic, metas)
     }
 
     fn invalidate_caches(&mut self) {
         self.cached_string = None;
         self.cached_width = None;
         self.cached_height = None;
+        self.cached_line_count = None;
+        self.cached_glyphs = None;
+        self.cached_carets = None;
     }
 
     fn calculate_dimensions(&mut self, context: &Context) -> (u32, u32) {
         let mut max_width = 0;
         let mut max_height = 0;
         {
-            let varied_section = self.generate_varied_section(context, Point2::new(0.0, 0.0), None);
-            let glyphed_section_texts = self.layout
-                .calculate_glyphs(context.gfx_context.glyph_brush.fonts(), &varied_section);
+            let mut text_arena = Vec::new();
+            let (varied_section, styles, _metas) = self.generate_varied_section(
+                context,
+                Point2::new(0.0, 0.0),
+                None,
+                &mut text_arena,
+                false,
+            );
+            let glyphed_section_texts = if styles.iter().any(Self::has_synthetic_style) {
+                let positioner = SyntheticGlyphPositioner {
+                    inner: self.layout,
+                    styles: &styles,
+                    measuring: true,
+                };
+                positioner.calculate_glyphs(context.gfx_context.glyph_brush.fonts(), &varied_section)
+            } else {
+                self.layout
+                    .calculate_glyphs(context.gfx_context.glyph_brush.fonts(), &varied_section)
+            };
             for glyphed_section_text in &glyphed_section_texts {
                 let &gfx_glyph::GlyphedSectionText(ref positioned_glyphs, ..) =
                     glyphed_section_text;
@@ -294,6 +749,231 @@ impl TextCached {
         (width, height)
     }
 
+    /// Runs the same `calculate_glyphs` pass as `calculate_dimensions`, but keeps each
+    /// positioned glyph's pixel bounding box paired with the byte index (into `contents()`)
+    /// of the grapheme cluster it renders. Glyphs coming from a right-to-left BiDi run are
+    /// mapped back through their piece's reversed order so the byte index still advances in
+    /// logical (reading) order; ligatures and other many-to-one glyph/grapheme mappings are
+    /// approximated 1:1, which holds for the vast majority of text.
+    fn calculate_glyph_geometry(&mut self, context: &Context) -> Vec<(usize, Rect)> {
+        if let Some(ref glyphs) = self.cached_glyphs {
+            return glyphs.clone();
+        }
+
+        let contents = self.contents();
+        let mut text_arena = Vec::new();
+        let (varied_section, styles, metas) = self.generate_varied_section(
+            context,
+            Point2::new(0.0, 0.0),
+            None,
+            &mut text_arena,
+            false,
+        );
+        let glyphed_section_texts = if styles.iter().any(Self::has_synthetic_style) {
+            let positioner = SyntheticGlyphPositioner {
+                inner: self.layout,
+                styles: &styles,
+                measuring: true,
+            };
+            positioner.calculate_glyphs(context.gfx_context.glyph_brush.fonts(), &varied_section)
+        } else {
+            self.layout
+                .calculate_glyphs(context.gfx_context.glyph_brush.fonts(), &varied_section)
+        };
+
+        let mut glyphs = Vec::new();
+        for (glyphed_section_text, meta) in glyphed_section_texts.iter().zip(&metas) {
+            let &gfx_glyph::GlyphedSectionText(ref positioned_glyphs, ..) = glyphed_section_text;
+            let source: Vec<usize> = contents[meta.source_start..meta.source_end]
+                .grapheme_indices(true)
+                .map(|(offset, _)| meta.source_start + offset)
+                .collect();
+            for (i, positioned_glyph) in positioned_glyphs.iter().enumerate() {
+                // Glyph `i` in render order maps to grapheme `i` in logical order normally,
+                // or back-to-front for a right-to-left piece.
+                let byte_index = if meta.is_rtl {
+                    source.get(source.len().wrapping_sub(i + 1)).cloned()
+                } else {
+                    source.get(i).cloned()
+                };
+                let byte_index = match byte_index {
+                    Some(idx) => idx,
+                    None => continue,
+                };
+                if let Some(bb) = positioned_glyph.pixel_bounding_box() {
+                    let rect = Rect::new(
+                        bb.min.x as f32,
+                        bb.min.y as f32,
+                        (bb.max.x - bb.min.x) as f32,
+                        (bb.max.y - bb.min.y) as f32,
+                    );
+                    glyphs.push((byte_index, rect));
+                }
+            }
+        }
+        glyphs.sort_by_key(|&(byte_index, _)| byte_index);
+        self.cached_glyphs = Some(glyphs.clone());
+        glyphs
+    }
+
+    /// Returns the pixel bounding box of every rendered glyph, paired with the byte index
+    /// (into `contents()`) of the grapheme cluster it represents. Intended for building
+    /// text input fields and selectable labels on top of `TextCached`.
+    pub fn glyph_rects(&mut self, context: &Context) -> Vec<(usize, Rect)> {
+        self.calculate_glyph_geometry(context)
+    }
+
+    /// Like `calculate_glyph_geometry`, but keyed on pen advance rather than ink. Every
+    /// glyph gets an entry - including whitespace and other grapheme clusters whose
+    /// `pixel_bounding_box()` is `None` and so are absent from `calculate_glyph_geometry` -
+    /// with `rect.x` the leading edge of the glyph and `rect.w` its advance width, so
+    /// `rect.x + rect.w` is the insertion point immediately after it. This is the geometry
+    /// `index_at_point` and `cursor_rect` need: hit-testing and caret placement have to work
+    /// between words, not just against drawn ink.
+    fn calculate_caret_geometry(&mut self, context: &Context) -> Vec<(usize, Rect)> {
+        if let Some(ref carets) = self.cached_carets {
+            return carets.clone();
+        }
+
+        let contents = self.contents();
+        let mut text_arena = Vec::new();
+        let (varied_section, styles, metas) = self.generate_varied_section(
+            context,
+            Point2::new(0.0, 0.0),
+            None,
+            &mut text_arena,
+            false,
+        );
+        let glyphed_section_texts = if styles.iter().any(Self::has_synthetic_style) {
+            let positioner = SyntheticGlyphPositioner {
+                inner: self.layout,
+                styles: &styles,
+                measuring: true,
+            };
+            positioner.calculate_glyphs(context.gfx_context.glyph_brush.fonts(), &varied_section)
+        } else {
+            self.layout
+                .calculate_glyphs(context.gfx_context.glyph_brush.fonts(), &varied_section)
+        };
+
+        let mut carets = Vec::new();
+        for (glyphed_section_text, meta) in glyphed_section_texts.iter().zip(&metas) {
+            let &gfx_glyph::GlyphedSectionText(ref positioned_glyphs, ..) = glyphed_section_text;
+            let source: Vec<usize> = contents[meta.source_start..meta.source_end]
+                .grapheme_indices(true)
+                .map(|(offset, _)| meta.source_start + offset)
+                .collect();
+            for (i, positioned_glyph) in positioned_glyphs.iter().enumerate() {
+                let byte_index = if meta.is_rtl {
+                    source.get(source.len().wrapping_sub(i + 1)).cloned()
+                } else {
+                    source.get(i).cloned()
+                };
+                let byte_index = match byte_index {
+                    Some(idx) => idx,
+                    None => continue,
+                };
+                let pos = positioned_glyph.position();
+                let advance = positioned_glyph.h_metrics().advance_width;
+                carets.push((byte_index, Rect::new(pos.x, pos.y, advance, 0.0)));
+            }
+        }
+        carets.sort_by_key(|&(byte_index, _)| byte_index);
+        self.cached_carets = Some(carets.clone());
+        carets
+    }
+
+    /// Maps a point (in the same pixel space as `glyph_rects`) to the byte index of the
+    /// nearest insertion point, by finding the glyph row closest to `p.y`, then comparing
+    /// `p.x` against each glyph's edges within that row: a point left of a glyph's midpoint
+    /// resolves to that glyph's own (leading) byte index, a point right of its midpoint
+    /// resolves to the byte index of the following grapheme. Returns the index just past the
+    /// last glyph on `p`'s row if `p` falls beyond it (the end of the text, on the last row),
+    /// and `None` only when there is no text at all.
+    pub fn index_at_point(&mut self, context: &Context, p: Point2) -> Option<usize> {
+        let carets = self.calculate_caret_geometry(context);
+        if carets.is_empty() {
+            return None;
+        }
+        let contents = self.contents();
+
+        // Find the row (baseline band) whose vertical extent is closest to `p.y`.
+        let metrics = self.font_metrics(context);
+        let line_height = metrics.line_height;
+        let band_distance = |baseline: f32| {
+            let (top, bottom) = (baseline - metrics.ascent, baseline - metrics.descent);
+            if p.y < top {
+                top - p.y
+            } else if p.y > bottom {
+                p.y - bottom
+            } else {
+                0.0
+            }
+        };
+        let (_, nearest) = carets
+            .iter()
+            .min_by(|(_, a), (_, b)| {
+                let da = band_distance(a.y);
+                let db = band_distance(b.y);
+                da.partial_cmp(&db).unwrap_or(::std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+        let row_y = nearest.y;
+
+        // Every glyph on that row, in left-to-right order.
+        let mut row: Vec<&(usize, Rect)> = carets
+            .iter()
+            .filter(|(_, rect)| (rect.y - row_y).abs() < line_height * 0.5)
+            .collect();
+        row.sort_by(|(_, a), (_, b)| a.x.partial_cmp(&b.x).unwrap_or(::std::cmp::Ordering::Equal));
+
+        if let Some(&&(last_index, last_rect)) = row.last() {
+            if p.x >= last_rect.x + last_rect.w {
+                let advance = contents[last_index..]
+                    .graphemes(true)
+                    .next()
+                    .map_or(0, str::len);
+                return Some(last_index + advance);
+            }
+        }
+
+        for &&(byte_index, rect) in &row {
+            let midpoint = rect.x + rect.w * 0.5;
+            if p.x < midpoint {
+                return Some(byte_index);
+            }
+            if p.x < rect.x + rect.w {
+                // Right of the midpoint but still within the glyph: caret goes after it,
+                // i.e. at the next grapheme's index.
+                let advance = contents[byte_index..].graphemes(true).next().map_or(0, str::len);
+                return Some(byte_index + advance);
+            }
+        }
+
+        row.first().map(|&&(byte_index, _)| byte_index)
+    }
+
+    /// Returns the caret geometry (position and height) for inserting at `byte_index`, i.e.
+    /// a thin `Rect` at the leading edge of the glyph starting there, or at the trailing
+    /// edge of the text when `byte_index == contents().len()`. Derived from
+    /// `calculate_caret_geometry`'s pen-advance positions rather than drawn ink, so this
+    /// resolves correctly even when `byte_index` points at a space or other glyph with no
+    /// visible bounding box.
+    pub fn cursor_rect(&mut self, context: &Context, byte_index: usize) -> Rect {
+        let carets = self.calculate_caret_geometry(context);
+        let line_height = self.font_metrics(context).line_height;
+
+        if let Some(&(_, rect)) = carets.iter().find(|&&(idx, _)| idx == byte_index) {
+            return Rect::new(rect.x, rect.y, 1.0, line_height);
+        }
+        // End-of-text (or an index past the last glyph): caret sits after the last glyph.
+        if let Some(&(_, rect)) = carets.last() {
+            return Rect::new(rect.x + rect.w, rect.y, 1.0, line_height);
+        }
+        // No text at all: caret sits at the text box's origin.
+        Rect::new(0.0, 0.0, 1.0, line_height)
+    }
+
     // TODO: doc better
     /// Calculates the width
     pub fn width(&mut self, context: &Context) -> u32 {
@@ -312,6 +992,51 @@ impl TextCached {
         }
     }
 
+    /// Returns metrics (ascent, descent, line gap, line height) for this `TextCached`'s
+    /// default font and scale, as reported by the underlying `rusttype` font.
+    pub fn font_metrics(&self, context: &Context) -> FontMetrics {
+        self.font_metrics_for(context, self.font_id, self.font_scale)
+    }
+
+    /// Same as `font_metrics`, but for an arbitrary font/scale pair rather than this
+    /// `TextCached`'s own defaults - used to measure the font/scale a fragment actually
+    /// resolved to, which may override both.
+    fn font_metrics_for(&self, context: &Context, font_id: FontId, scale: Scale) -> FontMetrics {
+        let font = &context.gfx_context.glyph_brush.fonts()[font_id];
+        let v_metrics = font.v_metrics(scale);
+        FontMetrics {
+            ascent: v_metrics.ascent,
+            descent: v_metrics.descent,
+            line_gap: v_metrics.line_gap,
+            line_height: v_metrics.ascent - v_metrics.descent + v_metrics.line_gap,
+        }
+    }
+
+    /// Returns the number of visual lines this text occupies, accounting for both explicit
+    /// line breaks and (if `set_bounds` wrapping is active) wrapping.
+    pub fn line_count(&mut self, context: &Context) -> usize {
+        if let Some(count) = self.cached_line_count {
+            return count;
+        }
+        let line_height = self.font_metrics(context).line_height;
+        let count = if self.bounds.x == f32::INFINITY {
+            // No wrapping in effect: lines are exactly the explicit line breaks.
+            self.contents().matches('\n').count() + 1
+        } else {
+            let height = self.height(context) as f32;
+            (height / line_height).ceil().max(1.0) as usize
+        };
+        self.cached_line_count = Some(count);
+        count
+    }
+
+    /// Returns the effective height, in pixels, of a single line for this text's default
+    /// font and scale. Handy for vertically centering multi-line text without having to
+    /// derive it from `height()` and `line_count()` by hand.
+    pub fn measured_line_height(&self, context: &Context) -> f32 {
+        self.font_metrics(context).line_height
+    }
+
     /// Returns the string that the text represents.
     pub fn contents(&mut self) -> String {
         if let Some(ref string) = self.cached_string {
@@ -324,14 +1049,31 @@ impl TextCached {
         string
     }
 
-    // TODO: figure out how to use font metrics to make it behave as `DrawParam::offset` does.
     /// Queues the `TextCached` to be drawn by `draw_queued()`.
     /// This is much more efficient than using `graphics::draw()` or equivalent.
     /// `relative_dest` is relative to the `DrawParam::dest` passed to `draw_queued()`.
     /// Note, any `TextCached` drawn via `graphics::draw()` will also draw the queue.
     pub fn queue(&self, context: &mut Context, relative_dest: Point2, color: Option<Color>) {
-        let varied_section = self.generate_varied_section(context, relative_dest, color);
-        context.gfx_context.glyph_brush.queue(varied_section);
+        let mut text_arena = Vec::new();
+        let (varied_section, styles, _metas) =
+            self.generate_varied_section(context, relative_dest, color, &mut text_arena, true);
+        if styles.iter().any(Self::has_synthetic_style) {
+            let positioner = SyntheticGlyphPositioner {
+                inner: self.layout,
+                styles: &styles,
+                measuring: false,
+            };
+            context
+                .gfx_context
+                .glyph_brush
+                .queue_custom_layout(varied_section, &positioner);
+        } else {
+            context.gfx_context.glyph_brush.queue(varied_section);
+        }
+    }
+
+    fn has_synthetic_style(style: &SyntheticStyle) -> bool {
+        style.embolden.is_some()
     }
 
     /// Exposes `gfx_glyph`'s `GlyphBrush::queue()` and `GlyphBrush::queue_custom_layout()`,
@@ -428,3 +1170,295 @@ impl Drawable for TextCached {
         self.blend_mode
     }
 }
+
+/// Parses `markup` into a flat `Vec<TextFragment>`, one fragment per contiguous style run.
+/// See `TextCached::from_markup` for the supported tag grammar.
+fn parse_markup(markup: &str, styles: &StyleTable) -> GameResult<Vec<TextFragment>> {
+    let mut fragments = Vec::new();
+    let mut buffer = String::new();
+    let mut color: Option<Color> = None;
+    let mut font_id: Option<FontId> = None;
+    let mut scale: Option<Scale> = None;
+    // Tags currently open, in nesting order: (name, byte offset it opened at, style to
+    // restore when its matching `[/name]` is seen).
+    let mut stack: Vec<(String, usize, Option<Color>, Option<FontId>, Option<Scale>)> = Vec::new();
+
+    let mut pos = 0;
+    while pos < markup.len() {
+        let tag_start = match markup[pos..].find('[') {
+            Some(rel) => pos + rel,
+            None => {
+                buffer.push_str(&markup[pos..]);
+                break;
+            }
+        };
+        buffer.push_str(&markup[pos..tag_start]);
+
+        let tag_end = match markup[tag_start..].find(']') {
+            Some(rel) => tag_start + rel,
+            None => {
+                // No closing bracket anywhere: the rest of the string is literal.
+                buffer.push_str(&markup[tag_start..]);
+                pos = markup.len();
+                continue;
+            }
+        };
+        let inner = &markup[tag_start + 1..tag_end];
+        pos = tag_end + 1;
+
+        if inner.starts_with('/') {
+            let name = &inner[1..];
+            if stack.last().map(|entry| entry.0.as_str()) == Some(name) {
+                flush_fragment(&mut buffer, &mut fragments, color, font_id, scale);
+                let (_, _, prev_color, prev_font, prev_scale) = stack.pop().unwrap();
+                color = prev_color;
+                font_id = prev_font;
+                scale = prev_scale;
+            } else {
+                // Close tag with nothing (matching) open: malformed, keep it literal.
+                buffer.push('[');
+                buffer.push_str(inner);
+                buffer.push(']');
+            }
+            continue;
+        }
+
+        let (name, value) = match inner.find('=') {
+            Some(eq) => (&inner[..eq], Some(&inner[eq + 1..])),
+            None => (inner, None),
+        };
+        let applied = match (name, value) {
+            ("color", Some(v)) => resolve_color(v, styles).map(|c| (Some(c), font_id, scale)),
+            ("scale", Some(v)) => v
+                .parse::<f32>()
+                .ok()
+                .map(|s| (color, font_id, Some(Scale::uniform(s)))),
+            ("font", Some(v)) => styles.font(v).map(|f| (color, Some(f), scale)),
+            _ => None,
+        };
+        match applied {
+            Some((new_color, new_font, new_scale)) => {
+                flush_fragment(&mut buffer, &mut fragments, color, font_id, scale);
+                stack.push((name.to_string(), tag_start, color, font_id, scale));
+                color = new_color;
+                font_id = new_font;
+                scale = new_scale;
+            }
+            None => {
+                // Unrecognized or malformed tag: emit it literally, as written.
+                buffer.push('[');
+                buffer.push_str(inner);
+                buffer.push(']');
+            }
+        }
+    }
+    flush_fragment(&mut buffer, &mut fragments, color, font_id, scale);
+
+    if !stack.is_empty() {
+        let unclosed: Vec<String> = stack
+            .into_iter()
+            .map(|(name, start, ..)| format!("[{}] at byte {}", name, start))
+            .collect();
+        return Err(GameError::FontError(format!(
+            "from_markup: unclosed tag(s) in {:?}: {}",
+            markup,
+            unclosed.join(", ")
+        )));
+    }
+
+    Ok(fragments)
+}
+
+fn flush_fragment(
+    buffer: &mut String,
+    fragments: &mut Vec<TextFragment>,
+    color: Option<Color>,
+    font_id: Option<FontId>,
+    scale: Option<Scale>,
+) {
+    if !buffer.is_empty() {
+        fragments.push(TextFragment {
+            text: ::std::mem::replace(buffer, String::new()),
+            color,
+            font_id,
+            scale,
+            embolden: None,
+        });
+    }
+}
+
+fn resolve_color(value: &str, styles: &StyleTable) -> Option<Color> {
+    if value.starts_with('#') {
+        parse_hex_color(&value[1..])
+    } else {
+        styles.color(value)
+    }
+}
+
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    let (r, g, b) = match hex.len() {
+        3 => (
+            channel(&hex[0..1].repeat(2))?,
+            channel(&hex[1..2].repeat(2))?,
+            channel(&hex[2..3].repeat(2))?,
+        ),
+        6 => (
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        ),
+        _ => return None,
+    };
+    Some(Color::from((r, g, b)))
+}
+
+#[cfg(test)]
+mod reorder_bidi_tests {
+    use super::*;
+
+    fn fragment(start: usize, end: usize) -> ResolvedFragment {
+        ResolvedFragment {
+            start,
+            end,
+            color: Color::new(1.0, 1.0, 1.0, 1.0),
+            font_id: FontId::default(),
+            scale: Scale::uniform(DEFAULT_FONT_SCALE),
+            embolden: None,
+        }
+    }
+
+    #[test]
+    fn ltr_text_is_left_unreordered() {
+        let text = TextCached::default();
+        let contents = "hello";
+        let pieces = text.reorder_bidi(contents, &[fragment(0, contents.len())]);
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].text, "hello");
+        assert!(!pieces[0].is_rtl);
+    }
+
+    #[test]
+    fn rtl_run_is_reversed_by_grapheme() {
+        let text = TextCached::default();
+        // "\u{5E9}\u{5DC}\u{5D5}\u{5DD}" is Hebrew for "shalom", a pure RTL run.
+        let contents = "\u{5E9}\u{5DC}\u{5D5}\u{5DD}";
+        let pieces = text.reorder_bidi(contents, &[fragment(0, contents.len())]);
+        assert_eq!(pieces.len(), 1);
+        assert!(pieces[0].is_rtl);
+        let expected: String = contents.graphemes(true).rev().collect();
+        assert_eq!(pieces[0].text, expected);
+    }
+
+    #[test]
+    fn rtl_run_is_still_reordered_with_wrapping_enabled() {
+        let mut text = TextCached::default();
+        text.set_bounds(Point2::new(200.0, f32::INFINITY), None);
+        let contents = "\u{5E9}\u{5DC}\u{5D5}\u{5DD}";
+        let pieces = text.reorder_bidi(contents, &[fragment(0, contents.len())]);
+        assert_eq!(pieces.len(), 1);
+        assert!(pieces[0].is_rtl);
+        let expected: String = contents.graphemes(true).rev().collect();
+        assert_eq!(pieces[0].text, expected);
+    }
+
+    #[test]
+    fn explicit_paragraphs_are_reordered_independently() {
+        let text = TextCached::default();
+        let para1 = "\u{5E9}\u{5DC}\u{5D5}\u{5DD}";
+        let para2 = "hello";
+        let contents = format!("{}\n{}", para1, para2);
+        let resolved = [fragment(0, contents.len())];
+        let pieces = text.reorder_bidi(&contents, &resolved);
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces[0].is_rtl);
+        assert!(!pieces[1].is_rtl);
+        assert!(pieces[1].text.ends_with("hello"));
+    }
+}
+
+#[cfg(test)]
+mod markup_tests {
+    use super::*;
+
+    #[test]
+    fn hex_color_six_digit() {
+        assert_eq!(parse_hex_color("ff0080"), Some(Color::from((0xff, 0x00, 0x80))));
+    }
+
+    #[test]
+    fn hex_color_three_digit_expands_each_nibble() {
+        assert_eq!(parse_hex_color("f08"), Some(Color::from((0xff, 0x00, 0x88))));
+    }
+
+    #[test]
+    fn hex_color_rejects_bad_length_and_digits() {
+        assert_eq!(parse_hex_color("ff00"), None);
+        assert_eq!(parse_hex_color("gggggg"), None);
+    }
+
+    #[test]
+    fn plain_text_is_a_single_fragment() {
+        let styles = StyleTable::new();
+        let fragments = parse_markup("hello world", &styles).unwrap();
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].text, "hello world");
+    }
+
+    #[test]
+    fn nested_tags_restore_outer_style_on_close() {
+        let styles = StyleTable::new();
+        let fragments = parse_markup("a[scale=20]b[color=#ff0000]c[/color]d[/scale]e", &styles)
+            .unwrap();
+        let texts: Vec<&str> = fragments.iter().map(|f| f.text.as_str()).collect();
+        assert_eq!(texts, vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(fragments[0].scale, None);
+        assert_eq!(fragments[1].scale, Some(Scale::uniform(20.0)));
+        assert_eq!(fragments[2].scale, Some(Scale::uniform(20.0)));
+        assert_eq!(fragments[2].color, Some(Color::from((0xff, 0x00, 0x00))));
+        assert_eq!(fragments[3].scale, Some(Scale::uniform(20.0)));
+        assert_eq!(fragments[3].color, None);
+        assert_eq!(fragments[4].scale, None);
+    }
+
+    #[test]
+    fn unknown_or_malformed_tags_degrade_to_literal_text() {
+        let styles = StyleTable::new();
+        let fragments = parse_markup("a[bogus=1]b[scale=notanumber]c[unclosed", &styles).unwrap();
+        let joined: String = fragments.iter().map(|f| f.text.as_str()).collect();
+        assert_eq!(joined, "a[bogus=1]b[scale=notanumber]c[unclosed");
+    }
+
+    #[test]
+    fn close_tag_with_nothing_open_is_literal() {
+        let styles = StyleTable::new();
+        let fragments = parse_markup("a[/scale]b", &styles).unwrap();
+        let joined: String = fragments.iter().map(|f| f.text.as_str()).collect();
+        assert_eq!(joined, "a[/scale]b");
+    }
+
+    #[test]
+    fn unclosed_tag_is_an_error() {
+        let styles = StyleTable::new();
+        let err = parse_markup("a[scale=20]b", &styles).unwrap_err();
+        match err {
+            GameError::FontError(msg) => assert!(msg.contains("scale")),
+            other => panic!("expected FontError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn named_font_and_color_resolve_via_style_table() {
+        let mut styles = StyleTable::new();
+        styles.add_font("title", FontId(1));
+        styles.add_color("warn", Color::from((0xff, 0xaa, 0x00)));
+        let fragments = parse_markup("[font=title][color=warn]hi[/color][/font]", &styles).unwrap();
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].text, "hi");
+        assert_eq!(fragments[0].font_id, Some(FontId(1)));
+        assert_eq!(fragments[0].color, Some(Color::from((0xff, 0xaa, 0x00))));
+    }
+}